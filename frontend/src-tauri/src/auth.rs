@@ -0,0 +1,406 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use keyring::Entry;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const SERVICE_NAME: &str = "alabama-auction-watcher";
+
+// Keyring username of the JSON index listing every known account.
+const ACCOUNTS_INDEX: &str = "accounts_index";
+// Keyring username holding the currently active account id.
+const ACTIVE_ACCOUNT: &str = "active_account";
+// Account id used when the caller never supplied one and none is active yet.
+const DEFAULT_ACCOUNT_ID: &str = "default";
+
+#[derive(Debug, Serialize)]
+pub struct AuthError {
+    code: String,
+    message: String,
+}
+
+impl AuthError {
+    pub fn new(code: &str, message: String) -> Self {
+        AuthError {
+            code: code.to_string(),
+            message,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    fn keyring(e: keyring::Error) -> Self {
+        AuthError::new("KEYRING_ERROR", format!("Failed to access keyring: {}", e))
+    }
+}
+
+// Summary of a stored account as surfaced to the frontend account picker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountSummary {
+    pub account_id: String,
+    pub label: String,
+    pub server_url: String,
+}
+
+// Per-account keyring usernames. Tokens for different logins never collide
+// because the account id is folded into the username.
+fn token_user(account_id: &str) -> String {
+    format!("auth_token:{}", account_id)
+}
+
+fn refresh_user(account_id: &str) -> String {
+    format!("refresh_token:{}", account_id)
+}
+
+fn expiry_user(account_id: &str) -> String {
+    format!("expires_at:{}", account_id)
+}
+
+// Current wall-clock time as a unix timestamp in seconds.
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+// Best-effort extraction of the `exp` claim from a JWT access token so we can
+// schedule a refresh without the caller telling us when it expires.
+fn parse_jwt_exp(token: &str) -> Option<i64> {
+    let payload = token.split('.').nth(1)?;
+    let bytes = URL_SAFE_NO_PAD.decode(payload).ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&bytes).ok()?;
+    claims.get("exp").and_then(|v| v.as_i64())
+}
+
+// Resolve which account a command operates on: the explicit id, else the
+// active account, else the built-in default.
+fn resolve_account_id(account_id: Option<String>) -> Result<String, AuthError> {
+    if let Some(id) = account_id {
+        return Ok(id);
+    }
+    if let Some(active) = read_active_account()? {
+        return Ok(active);
+    }
+    Ok(DEFAULT_ACCOUNT_ID.to_string())
+}
+
+fn read_active_account() -> Result<Option<String>, AuthError> {
+    let entry = Entry::new(SERVICE_NAME, ACTIVE_ACCOUNT).map_err(AuthError::keyring)?;
+    match entry.get_password() {
+        Ok(id) => Ok(Some(id)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(AuthError::new(
+            "RETRIEVE_ERROR",
+            format!("Failed to read active account: {}", e),
+        )),
+    }
+}
+
+fn read_index() -> Result<Vec<AccountSummary>, AuthError> {
+    let entry = Entry::new(SERVICE_NAME, ACCOUNTS_INDEX).map_err(AuthError::keyring)?;
+    match entry.get_password() {
+        Ok(json) => serde_json::from_str(&json).map_err(|e| {
+            AuthError::new(
+                "DECODE_ERROR",
+                format!("Failed to parse accounts index: {}", e),
+            )
+        }),
+        Err(keyring::Error::NoEntry) => Ok(Vec::new()),
+        Err(e) => Err(AuthError::new(
+            "RETRIEVE_ERROR",
+            format!("Failed to read accounts index: {}", e),
+        )),
+    }
+}
+
+fn write_index(accounts: &[AccountSummary]) -> Result<(), AuthError> {
+    let entry = Entry::new(SERVICE_NAME, ACCOUNTS_INDEX).map_err(AuthError::keyring)?;
+    let json = serde_json::to_string(accounts).map_err(|e| {
+        AuthError::new(
+            "ENCODE_ERROR",
+            format!("Failed to serialize accounts index: {}", e),
+        )
+    })?;
+    entry.set_password(&json).map_err(|e| {
+        AuthError::new(
+            "STORE_ERROR",
+            format!("Failed to store accounts index: {}", e),
+        )
+    })
+}
+
+// Add the account to the index if it isn't already present, filling the label
+// and server url with sensible defaults when we only learned the id.
+fn upsert_account(account_id: &str, server_url: &str) -> Result<(), AuthError> {
+    // The synthetic default id is only a fallback for lone-login setups; never
+    // index it, or a later real (e.g. OAuth) login would leave a phantom
+    // `{account_id:"default", label:"default"}` row in `list_accounts` forever.
+    if account_id == DEFAULT_ACCOUNT_ID {
+        return Ok(());
+    }
+    let mut index = read_index()?;
+    if index.iter().any(|a| a.account_id == account_id) {
+        return Ok(());
+    }
+    index.push(AccountSummary {
+        account_id: account_id.to_string(),
+        label: account_id.to_string(),
+        server_url: server_url.to_string(),
+    });
+    write_index(&index)
+}
+
+// Store authentication tokens securely using the system keyring, scoped to a
+// single account. Falls back to the active (or default) account when no
+// `account_id` is given. When `expires_at` is omitted we fall back to the JWT
+// `exp` claim so the refresh scheduler still has something to work with.
+#[tauri::command]
+pub fn store_auth_token(
+    token: String,
+    refresh_token: Option<String>,
+    account_id: Option<String>,
+    expires_at: Option<i64>,
+) -> Result<bool, AuthError> {
+    let account_id = resolve_account_id(account_id)?;
+    store_tokens(&account_id, &token, refresh_token.as_deref(), expires_at)?;
+
+    // First account in becomes the active one so lone-login setups keep working.
+    if read_active_account()?.is_none() {
+        set_active_account(account_id)?;
+    }
+
+    Ok(true)
+}
+
+// Write a token triplet for an account without touching active-account state.
+// Shared by the store command and the refresh subsystem.
+pub(crate) fn store_tokens(
+    account_id: &str,
+    token: &str,
+    refresh_token: Option<&str>,
+    expires_at: Option<i64>,
+) -> Result<(), AuthError> {
+    // Fall back to the JWT `exp` claim for every write path (store/refresh/
+    // OAuth), so a server that omits `expires_at` still leaves the scheduler
+    // something to track.
+    let expires_at = expires_at.or_else(|| parse_jwt_exp(token));
+
+    let entry = Entry::new(SERVICE_NAME, &token_user(account_id)).map_err(AuthError::keyring)?;
+    entry
+        .set_password(token)
+        .map_err(|e| AuthError::new("STORE_ERROR", format!("Failed to store token: {}", e)))?;
+
+    if let Some(refresh) = refresh_token {
+        let refresh_entry = Entry::new(SERVICE_NAME, &refresh_user(account_id)).map_err(|e| {
+            AuthError::new(
+                "KEYRING_ERROR",
+                format!("Failed to access keyring for refresh token: {}", e),
+            )
+        })?;
+        refresh_entry.set_password(refresh).map_err(|e| {
+            AuthError::new(
+                "STORE_ERROR",
+                format!("Failed to store refresh token: {}", e),
+            )
+        })?;
+    }
+
+    let expiry_entry =
+        Entry::new(SERVICE_NAME, &expiry_user(account_id)).map_err(AuthError::keyring)?;
+    match expires_at {
+        Some(exp) => expiry_entry.set_password(&exp.to_string()).map_err(|e| {
+            AuthError::new("STORE_ERROR", format!("Failed to store token expiry: {}", e))
+        })?,
+        None => {
+            let _ = expiry_entry.delete_credential();
+        }
+    }
+
+    upsert_account(account_id, crate::default_server_url())
+}
+
+// Read the stored refresh token for an account, if any.
+pub(crate) fn get_refresh_token(account_id: &str) -> Result<Option<String>, AuthError> {
+    let entry = Entry::new(SERVICE_NAME, &refresh_user(account_id)).map_err(AuthError::keyring)?;
+    match entry.get_password() {
+        Ok(token) => Ok(Some(token)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(AuthError::new(
+            "RETRIEVE_ERROR",
+            format!("Failed to retrieve refresh token: {}", e),
+        )),
+    }
+}
+
+// Read the persisted access-token expiry for an account, if known.
+pub(crate) fn read_expiry(account_id: &str) -> Result<Option<i64>, AuthError> {
+    let entry = Entry::new(SERVICE_NAME, &expiry_user(account_id)).map_err(AuthError::keyring)?;
+    match entry.get_password() {
+        Ok(raw) => Ok(raw.parse::<i64>().ok()),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(AuthError::new(
+            "RETRIEVE_ERROR",
+            format!("Failed to retrieve token expiry: {}", e),
+        )),
+    }
+}
+
+// Whether the access token for an account has passed its expiry.
+pub(crate) fn is_expired(account_id: &str) -> Result<bool, AuthError> {
+    Ok(read_expiry(account_id)?
+        .map(|exp| now_unix() >= exp)
+        .unwrap_or(false))
+}
+
+// Resolve the server url recorded for an account, defaulting to the build-time
+// endpoint when the account is unknown.
+pub(crate) fn account_server_url(account_id: &str) -> Result<String, AuthError> {
+    Ok(read_index()?
+        .into_iter()
+        .find(|a| a.account_id == account_id)
+        .map(|a| a.server_url)
+        .unwrap_or_else(|| crate::default_server_url().to_string()))
+}
+
+// Insert or update a full account summary in the index. Used by flows (such as
+// OAuth) that learn the label and server url alongside the tokens.
+pub(crate) fn register_account(summary: AccountSummary) -> Result<(), AuthError> {
+    let mut index = read_index()?;
+    match index.iter_mut().find(|a| a.account_id == summary.account_id) {
+        Some(existing) => {
+            existing.label = summary.label;
+            existing.server_url = summary.server_url;
+        }
+        None => index.push(summary),
+    }
+    write_index(&index)
+}
+
+// Look up an account summary by id.
+pub(crate) fn find_account(account_id: &str) -> Result<Option<AccountSummary>, AuthError> {
+    Ok(read_index()?
+        .into_iter()
+        .find(|a| a.account_id == account_id))
+}
+
+// Retrieve the authentication token for an account from the system keyring.
+#[tauri::command]
+pub fn get_auth_token(account_id: Option<String>) -> Result<Option<String>, AuthError> {
+    let account_id = resolve_account_id(account_id)?;
+
+    // If the cached token has already expired, try to refresh it in-line. A
+    // failed refresh is ignored and the stored (expired) token is returned.
+    if is_expired(&account_id)? {
+        let _ = crate::refresh::refresh_now(&account_id);
+    }
+
+    let entry = Entry::new(SERVICE_NAME, &token_user(&account_id)).map_err(AuthError::keyring)?;
+
+    match entry.get_password() {
+        Ok(password) => Ok(Some(password)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(AuthError::new(
+            "RETRIEVE_ERROR",
+            format!("Failed to retrieve token: {}", e),
+        )),
+    }
+}
+
+// Clear the stored authentication tokens for an account.
+#[tauri::command]
+pub fn clear_auth_tokens(account_id: Option<String>) -> Result<bool, AuthError> {
+    let account_id = resolve_account_id(account_id)?;
+
+    if let Ok(entry) = Entry::new(SERVICE_NAME, &token_user(&account_id)) {
+        let _ = entry.delete_credential();
+    }
+    if let Ok(entry) = Entry::new(SERVICE_NAME, &refresh_user(&account_id)) {
+        let _ = entry.delete_credential();
+    }
+    // Drop the expiry too, or `get_auth_token` would read a stale expiry for the
+    // now-absent token and fire a pointless refresh.
+    if let Ok(entry) = Entry::new(SERVICE_NAME, &expiry_user(&account_id)) {
+        let _ = entry.delete_credential();
+    }
+
+    // Remove the account from the index so it no longer shows in `list_accounts`.
+    let mut index = read_index()?;
+    index.retain(|a| a.account_id != account_id);
+    write_index(&index)?;
+
+    // If the cleared account was active, clear (or move) the active pointer.
+    if read_active_account()? == Some(account_id.clone()) {
+        match index.first() {
+            Some(next) => set_active_account(next.account_id.clone())?,
+            None => {
+                if let Ok(entry) = Entry::new(SERVICE_NAME, ACTIVE_ACCOUNT) {
+                    let _ = entry.delete_credential();
+                }
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+// List every account known to this installation.
+#[tauri::command]
+pub fn list_accounts() -> Result<Vec<AccountSummary>, AuthError> {
+    read_index()
+}
+
+// Switch the account that token commands default to.
+#[tauri::command]
+pub fn set_active_account(account_id: String) -> Result<(), AuthError> {
+    let entry = Entry::new(SERVICE_NAME, ACTIVE_ACCOUNT).map_err(AuthError::keyring)?;
+    entry.set_password(&account_id).map_err(|e| {
+        AuthError::new(
+            "STORE_ERROR",
+            format!("Failed to set active account: {}", e),
+        )
+    })
+}
+
+// Return the currently active account id, if one has been selected.
+#[tauri::command]
+pub fn get_active_account() -> Result<Option<String>, AuthError> {
+    read_active_account()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Build a JWT-shaped `header.payload.signature` string from a claims object;
+    // only the payload segment matters to `parse_jwt_exp`.
+    fn jwt_with_payload(payload: &serde_json::Value) -> String {
+        let header = URL_SAFE_NO_PAD.encode(br#"{"alg":"HS256","typ":"JWT"}"#);
+        let body = URL_SAFE_NO_PAD.encode(serde_json::to_vec(payload).unwrap());
+        format!("{}.{}.{}", header, body, "sig")
+    }
+
+    #[test]
+    fn parses_exp_claim() {
+        let token = jwt_with_payload(&serde_json::json!({ "sub": "u1", "exp": 1_700_000_000i64 }));
+        assert_eq!(parse_jwt_exp(&token), Some(1_700_000_000));
+    }
+
+    #[test]
+    fn missing_exp_claim_is_none() {
+        let token = jwt_with_payload(&serde_json::json!({ "sub": "u1" }));
+        assert_eq!(parse_jwt_exp(&token), None);
+    }
+
+    #[test]
+    fn non_jwt_input_is_none() {
+        assert_eq!(parse_jwt_exp("not-a-jwt"), None);
+        assert_eq!(parse_jwt_exp(""), None);
+        assert_eq!(parse_jwt_exp("only.two"), None);
+    }
+}
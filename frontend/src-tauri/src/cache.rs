@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use tauri::{AppHandle, Manager, State};
+use zeroize::Zeroizing;
+
+use crate::error::AppError;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const KEY_LEN: usize = 32;
+const CACHE_FILE: &str = "cache.bin";
+
+// The derived cache key, held only while the cache is unlocked and zeroized on
+// lock. Salt and data live on disk; the key never leaves memory.
+pub struct CacheKey(pub Mutex<Option<Zeroizing<[u8; KEY_LEN]>>>);
+
+impl CacheKey {
+    pub fn new() -> Self {
+        CacheKey(Mutex::new(None))
+    }
+}
+
+impl Default for CacheKey {
+    fn default() -> Self {
+        CacheKey::new()
+    }
+}
+
+fn state_error(e: impl std::fmt::Display) -> AppError {
+    AppError::new("STATE_ERROR", format!("Cache state poisoned: {}", e))
+}
+
+fn crypto_error(msg: &str) -> AppError {
+    AppError::new("CACHE_ERROR", msg.to_string())
+}
+
+fn cache_path(app: &AppHandle) -> Result<PathBuf, AppError> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::new("PATH_ERROR", format!("No app data dir: {}", e)))?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| AppError::new("IO_ERROR", format!("Failed to create data dir: {}", e)))?;
+    Ok(dir.join(CACHE_FILE))
+}
+
+// Derive a 32-byte key from the passphrase and salt with Argon2id.
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Zeroizing<[u8; KEY_LEN]>, AppError> {
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+        .map_err(|e| AppError::new("CACHE_ERROR", format!("Key derivation failed: {}", e)))?;
+    Ok(key)
+}
+
+// Serialize and encrypt the map to a `salt || nonce || ciphertext` blob.
+fn encrypt_map(
+    key: &[u8; KEY_LEN],
+    salt: &[u8; SALT_LEN],
+    map: &HashMap<String, String>,
+) -> Result<Vec<u8>, AppError> {
+    let plaintext = serde_json::to_vec(map)
+        .map_err(|e| AppError::new("ENCODE_ERROR", format!("Failed to serialize cache: {}", e)))?;
+
+    let cipher = XChaCha20Poly1305::new(key.into());
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = XNonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext.as_ref())
+        .map_err(|_| crypto_error("Encryption failed"))?;
+
+    let mut blob = Vec::with_capacity(SALT_LEN + NONCE_LEN + ciphertext.len());
+    blob.extend_from_slice(salt);
+    blob.extend_from_slice(&nonce_bytes);
+    blob.extend_from_slice(&ciphertext);
+    Ok(blob)
+}
+
+// Authenticate and decrypt a blob produced by `encrypt_map`. The Poly1305 tag
+// is verified by `decrypt`, so a wrong key or tampered blob is rejected here.
+fn decrypt_blob(
+    key: &[u8; KEY_LEN],
+    blob: &[u8],
+) -> Result<HashMap<String, String>, AppError> {
+    if blob.len() < SALT_LEN + NONCE_LEN {
+        return Err(crypto_error("Cache blob is truncated"));
+    }
+    let nonce = XNonce::from_slice(&blob[SALT_LEN..SALT_LEN + NONCE_LEN]);
+    let ciphertext = &blob[SALT_LEN + NONCE_LEN..];
+
+    let cipher = XChaCha20Poly1305::new(key.into());
+    let plaintext = cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|_| crypto_error("Decryption failed: wrong passphrase or corrupt cache"))?;
+
+    serde_json::from_slice(&plaintext)
+        .map_err(|e| AppError::new("DECODE_ERROR", format!("Invalid cache contents: {}", e)))
+}
+
+// Load and decrypt the on-disk map with the unlocked key, or an empty map when
+// no cache file exists yet.
+fn load_map(app: &AppHandle, key: &[u8; KEY_LEN]) -> Result<HashMap<String, String>, AppError> {
+    let path = cache_path(app)?;
+    match std::fs::read(&path) {
+        Ok(blob) => decrypt_blob(key, &blob),
+        Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+        Err(e) => Err(AppError::new("IO_ERROR", format!("Failed to read cache: {}", e))),
+    }
+}
+
+// Read the salt stored in an existing cache file, if any.
+fn read_salt(app: &AppHandle) -> Result<Option<[u8; SALT_LEN]>, AppError> {
+    let path = cache_path(app)?;
+    match std::fs::read(&path) {
+        Ok(blob) if blob.len() >= SALT_LEN => {
+            let mut salt = [0u8; SALT_LEN];
+            salt.copy_from_slice(&blob[..SALT_LEN]);
+            Ok(Some(salt))
+        }
+        Ok(_) => Err(crypto_error("Cache blob is truncated")),
+        Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(AppError::new("IO_ERROR", format!("Failed to read cache: {}", e))),
+    }
+}
+
+// Encrypt and persist the map using the current salt.
+fn save_map(
+    app: &AppHandle,
+    key: &[u8; KEY_LEN],
+    salt: &[u8; SALT_LEN],
+    map: &HashMap<String, String>,
+) -> Result<(), AppError> {
+    let blob = encrypt_map(key, salt, map)?;
+    std::fs::write(cache_path(app)?, blob)
+        .map_err(|e| AppError::new("IO_ERROR", format!("Failed to write cache: {}", e)))
+}
+
+fn with_key<F, T>(state: &State<CacheKey>, f: F) -> Result<T, AppError>
+where
+    F: FnOnce(&[u8; KEY_LEN]) -> Result<T, AppError>,
+{
+    let guard = state.0.lock().map_err(state_error)?;
+    let key = guard
+        .as_ref()
+        .ok_or_else(|| AppError::new("LOCKED", "Cache is locked".to_string()))?;
+    f(key)
+}
+
+// Unlock the cache with a passphrase. For an existing cache this both derives
+// the key and verifies the passphrase by decrypting the stored blob, returning
+// `false` on mismatch; for a fresh cache it writes an empty encrypted store.
+#[tauri::command]
+pub fn unlock_cache(
+    app: AppHandle,
+    state: State<CacheKey>,
+    passphrase: String,
+) -> Result<bool, AppError> {
+    match read_salt(&app)? {
+        Some(salt) => {
+            let key = derive_key(&passphrase, &salt)?;
+            if decrypt_blob(&key, &std::fs::read(cache_path(&app)?).unwrap_or_default()).is_err() {
+                return Ok(false);
+            }
+            *state.0.lock().map_err(state_error)? = Some(key);
+            Ok(true)
+        }
+        None => {
+            let mut salt = [0u8; SALT_LEN];
+            OsRng.fill_bytes(&mut salt);
+            let key = derive_key(&passphrase, &salt)?;
+            save_map(&app, &key, &salt, &HashMap::new())?;
+            *state.0.lock().map_err(state_error)? = Some(key);
+            Ok(true)
+        }
+    }
+}
+
+// Store a JSON value under a key in the unlocked cache.
+#[tauri::command]
+pub fn cache_put(
+    app: AppHandle,
+    state: State<CacheKey>,
+    key: String,
+    json: String,
+) -> Result<(), AppError> {
+    let salt = read_salt(&app)?
+        .ok_or_else(|| AppError::new("LOCKED", "Cache has not been initialized".to_string()))?;
+    with_key(&state, |k| {
+        let mut map = load_map(&app, k)?;
+        map.insert(key, json);
+        save_map(&app, k, &salt, &map)
+    })
+}
+
+// Read a JSON value from the unlocked cache.
+#[tauri::command]
+pub fn cache_get(
+    app: AppHandle,
+    state: State<CacheKey>,
+    key: String,
+) -> Result<Option<String>, AppError> {
+    with_key(&state, |k| Ok(load_map(&app, k)?.get(&key).cloned()))
+}
+
+// Lock the cache, zeroizing the in-memory key.
+#[tauri::command]
+pub fn lock_cache(state: State<CacheKey>) -> Result<(), AppError> {
+    *state.0.lock().map_err(state_error)? = None;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_map() -> HashMap<String, String> {
+        let mut map = HashMap::new();
+        map.insert("auction:42".to_string(), r#"{"bid":1200}"#.to_string());
+        map.insert("county".to_string(), "Baldwin".to_string());
+        map
+    }
+
+    #[test]
+    fn roundtrip_recovers_the_map() {
+        let key = [7u8; KEY_LEN];
+        let salt = [3u8; SALT_LEN];
+        let map = sample_map();
+
+        let blob = encrypt_map(&key, &salt, &map).expect("encrypt");
+        assert_eq!(&blob[..SALT_LEN], &salt, "salt is prepended");
+
+        let decoded = decrypt_blob(&key, &blob).expect("decrypt");
+        assert_eq!(decoded, map);
+    }
+
+    #[test]
+    fn wrong_key_is_rejected() {
+        let salt = [3u8; SALT_LEN];
+        let blob = encrypt_map(&[7u8; KEY_LEN], &salt, &sample_map()).expect("encrypt");
+
+        assert!(decrypt_blob(&[9u8; KEY_LEN], &blob).is_err());
+    }
+
+    #[test]
+    fn tampered_tag_is_rejected() {
+        let key = [7u8; KEY_LEN];
+        let salt = [3u8; SALT_LEN];
+        let mut blob = encrypt_map(&key, &salt, &sample_map()).expect("encrypt");
+
+        // Flip a bit in the ciphertext/tag region; authentication must fail.
+        let last = blob.len() - 1;
+        blob[last] ^= 0x01;
+        assert!(decrypt_blob(&key, &blob).is_err());
+    }
+
+    #[test]
+    fn truncated_blob_is_rejected() {
+        let key = [7u8; KEY_LEN];
+        assert!(decrypt_blob(&key, &[0u8; SALT_LEN]).is_err());
+    }
+
+    #[test]
+    fn derived_key_is_deterministic() {
+        let salt = [5u8; SALT_LEN];
+        let a = derive_key("correct horse", &salt).expect("derive");
+        let b = derive_key("correct horse", &salt).expect("derive");
+        assert_eq!(a.as_ref(), b.as_ref());
+
+        let other = derive_key("wrong horse", &salt).expect("derive");
+        assert_ne!(a.as_ref(), other.as_ref());
+    }
+}
@@ -0,0 +1,34 @@
+use serde::Serialize;
+
+use crate::auth::AuthError;
+
+// Error surfaced to the frontend by the non-auth subsystems (cache, settings,
+// watcher). Same `{code, message}` shape as `AuthError`, but a distinct type so
+// cache crypto / settings IO / watcher failures aren't mislabelled as auth
+// problems in frontend error handling.
+#[derive(Debug, Serialize)]
+pub struct AppError {
+    code: String,
+    message: String,
+}
+
+impl AppError {
+    pub fn new(code: &str, message: String) -> Self {
+        AppError {
+            code: code.to_string(),
+            message,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+// Auth failures reached through shared auth helpers (e.g. the watcher fetching a
+// token) fold into an `AppError`, preserving the original code and message.
+impl From<AuthError> for AppError {
+    fn from(e: AuthError) -> Self {
+        AppError::new(e.code(), e.message().to_string())
+    }
+}
@@ -1,7 +1,12 @@
-use keyring::Entry;
 use serde::{Deserialize, Serialize};
 
-const SERVICE_NAME: &str = "alabama-auction-watcher";
+mod auth;
+mod cache;
+mod error;
+mod oauth;
+mod refresh;
+mod settings;
+mod watcher;
 
 // Server URL from build-time environment variable, with fallback for development
 const DEFAULT_SERVER_URL: &str = "http://localhost:8001";
@@ -13,82 +18,11 @@ pub struct ServerInfo {
     tauri_version: String,
 }
 
-#[derive(Debug, Serialize)]
-pub struct AuthError {
-    code: String,
-    message: String,
-}
-
-// Store authentication token securely using system keyring
-#[tauri::command]
-fn store_auth_token(token: String, refresh_token: Option<String>) -> Result<bool, AuthError> {
-    let entry = Entry::new(SERVICE_NAME, "auth_token").map_err(|e| AuthError {
-        code: "KEYRING_ERROR".to_string(),
-        message: format!("Failed to access keyring: {}", e),
-    })?;
-
-    entry.set_password(&token).map_err(|e| AuthError {
-        code: "STORE_ERROR".to_string(),
-        message: format!("Failed to store token: {}", e),
-    })?;
-
-    if let Some(refresh) = refresh_token {
-        let refresh_entry = Entry::new(SERVICE_NAME, "refresh_token").map_err(|e| AuthError {
-            code: "KEYRING_ERROR".to_string(),
-            message: format!("Failed to access keyring for refresh token: {}", e),
-        })?;
-        refresh_entry.set_password(&refresh).map_err(|e| AuthError {
-            code: "STORE_ERROR".to_string(),
-            message: format!("Failed to store refresh token: {}", e),
-        })?;
-    }
-
-    Ok(true)
-}
-
-// Retrieve authentication token from system keyring
-#[tauri::command]
-fn get_auth_token() -> Result<Option<String>, AuthError> {
-    let entry = Entry::new(SERVICE_NAME, "auth_token").map_err(|e| AuthError {
-        code: "KEYRING_ERROR".to_string(),
-        message: format!("Failed to access keyring: {}", e),
-    })?;
-
-    match entry.get_password() {
-        Ok(password) => Ok(Some(password)),
-        Err(keyring::Error::NoEntry) => Ok(None),
-        Err(e) => Err(AuthError {
-            code: "RETRIEVE_ERROR".to_string(),
-            message: format!("Failed to retrieve token: {}", e),
-        }),
-    }
-}
-
-// Clear stored authentication tokens
-#[tauri::command]
-fn clear_auth_tokens() -> Result<bool, AuthError> {
-    if let Ok(entry) = Entry::new(SERVICE_NAME, "auth_token") {
-        let _ = entry.delete_credential();
-    }
-    if let Ok(entry) = Entry::new(SERVICE_NAME, "refresh_token") {
-        let _ = entry.delete_credential();
-    }
-    Ok(true)
-}
-
-// Get server configuration info
-#[tauri::command]
-fn get_server_info() -> ServerInfo {
-    // Use environment variable at build time, fall back to default
-    let server_url = option_env!("TAURI_API_URL")
-        .unwrap_or(DEFAULT_SERVER_URL)
-        .to_string();
-
-    ServerInfo {
-        server_url,
-        is_development: cfg!(debug_assertions),
-        tauri_version: env!("CARGO_PKG_VERSION").to_string(),
-    }
+// Build-time default server url, shared with the account, OAuth and watcher
+// subsystems. It does not read the `server_url` override from the settings
+// store; only `get_server_info` applies that override.
+pub(crate) fn default_server_url() -> &'static str {
+    option_env!("TAURI_API_URL").unwrap_or(DEFAULT_SERVER_URL)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -99,6 +33,10 @@ pub fn run() {
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_os::init())
+        .plugin(tauri_plugin_notification::init())
+        .manage(watcher::WatchState::new())
+        .manage(cache::CacheKey::new())
+        .manage(settings::SettingsStore::new())
         .setup(|app| {
             if cfg!(debug_assertions) {
                 app.handle().plugin(
@@ -107,13 +45,57 @@ pub fn run() {
                         .build(),
                 )?;
             }
+            if let Some(store) = app.try_state::<settings::SettingsStore>() {
+                settings::load(app.handle(), store.inner())
+                    .map_err(|e| e.message().to_string())?;
+            }
+            settings::spawn_autosave(app.handle().clone());
+
+            refresh::spawn_scheduler(app.handle().clone());
+
+            #[cfg(desktop)]
+            {
+                use tauri_plugin_global_shortcut::{
+                    Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState,
+                };
+
+                app.handle()
+                    .plugin(tauri_plugin_global_shortcut::Builder::new().build())?;
+                let toggle = Shortcut::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::KeyA);
+                app.global_shortcut()
+                    .on_shortcut(toggle, |app, _shortcut, event| {
+                        if event.state() == ShortcutState::Pressed {
+                            watcher::toggle_main_window(app);
+                        }
+                    })?;
+
+                watcher::setup_tray(app.handle())?;
+                watcher::spawn_watcher(app.handle().clone());
+            }
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
-            store_auth_token,
-            get_auth_token,
-            clear_auth_tokens,
-            get_server_info,
+            auth::store_auth_token,
+            auth::get_auth_token,
+            auth::clear_auth_tokens,
+            auth::list_accounts,
+            auth::set_active_account,
+            auth::get_active_account,
+            oauth::begin_oauth_login,
+            watcher::set_watch_interval,
+            watcher::set_notification_leads,
+            cache::unlock_cache,
+            cache::cache_put,
+            cache::cache_get,
+            cache::lock_cache,
+            settings::settings_get,
+            settings::settings_set,
+            settings::settings_delete,
+            settings::saved_searches_list,
+            settings::saved_searches_add,
+            settings::saved_searches_remove,
+            settings::get_server_info,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
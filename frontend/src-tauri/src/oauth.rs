@@ -0,0 +1,199 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use axum::{extract::Query, extract::State, response::Html, routing::get, Router};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+use tokio::sync::oneshot;
+
+use crate::auth::{self, AccountSummary, AuthError};
+
+// How long the loopback server waits for the provider redirect before giving
+// up, so an abandoned browser login doesn't leak the listener until app exit.
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+// What `begin_oauth_login` hands back to the frontend so it can show progress
+// and, if needed, surface the url for manual opening.
+#[derive(Debug, Serialize)]
+pub struct OauthBegin {
+    auth_url: String,
+    port: u16,
+}
+
+// Query string the provider appends to our loopback redirect.
+#[derive(Debug, Deserialize)]
+struct CallbackParams {
+    code: Option<String>,
+    state: Option<String>,
+}
+
+// Response body of `{server_url}/auth/callback` once we exchange the code.
+#[derive(Debug, Deserialize)]
+struct CallbackResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_at: Option<i64>,
+    account: AccountSummary,
+}
+
+// Shared state for the single-shot callback handler.
+struct CallbackCtx {
+    app: AppHandle,
+    server_url: String,
+    expected_state: String,
+    shutdown: Mutex<Option<oneshot::Sender<()>>>,
+}
+
+// Weak, non-secret CSRF/state token. The redirect only ever reaches localhost,
+// so a timestamp-derived nonce is enough to tie a callback to its request.
+fn random_state() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    format!("{:x}", nanos)
+}
+
+// Start browser-based OAuth: bind a throwaway loopback listener, open the
+// provider's hosted login page, and return the url/port to the frontend.
+#[tauri::command]
+pub fn begin_oauth_login(app: AppHandle) -> Result<OauthBegin, AuthError> {
+    let server_url = match auth::get_active_account()? {
+        Some(id) => auth::account_server_url(&id)?,
+        None => crate::default_server_url().to_string(),
+    };
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").map_err(|e| {
+        AuthError::new("OAUTH_ERROR", format!("Failed to bind loopback server: {}", e))
+    })?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| AuthError::new("OAUTH_ERROR", format!("Failed to read local port: {}", e)))?
+        .port();
+
+    let state = random_state();
+    let auth_url = format!(
+        "{}/auth/authorize?response_type=code&redirect_uri=http%3A%2F%2F127.0.0.1%3A{}%2Fcallback&state={}",
+        server_url, port, state
+    );
+
+    tauri::async_runtime::spawn(serve(app.clone(), listener, server_url, state));
+
+    app.shell()
+        .open(&auth_url, None)
+        .map_err(|e| AuthError::new("OAUTH_ERROR", format!("Failed to open browser: {}", e)))?;
+
+    Ok(OauthBegin { auth_url, port })
+}
+
+// Run the loopback server until it handles one redirect or `LOGIN_TIMEOUT`
+// passes.
+async fn serve(
+    app: AppHandle,
+    std_listener: std::net::TcpListener,
+    server_url: String,
+    expected_state: String,
+) {
+    let (tx, rx) = oneshot::channel();
+    let ctx = Arc::new(CallbackCtx {
+        app,
+        server_url,
+        expected_state,
+        shutdown: Mutex::new(Some(tx)),
+    });
+
+    let router = Router::new()
+        .route("/callback", get(handle_callback))
+        .with_state(ctx);
+
+    if std_listener.set_nonblocking(true).is_err() {
+        return;
+    }
+    let listener = match tokio::net::TcpListener::from_std(std_listener) {
+        Ok(l) => l,
+        Err(_) => return,
+    };
+
+    let _ = axum::serve(listener, router)
+        .with_graceful_shutdown(async move {
+            // Shut down once the callback fires, or after the timeout if the
+            // user abandons the login and no redirect ever arrives.
+            let _ = tokio::time::timeout(LOGIN_TIMEOUT, rx).await;
+        })
+        .await;
+}
+
+// Handle the provider redirect: validate state, exchange the code for tokens,
+// persist them, and emit `login-complete`.
+async fn handle_callback(
+    State(ctx): State<Arc<CallbackCtx>>,
+    Query(params): Query<CallbackParams>,
+) -> Html<String> {
+    let result = exchange(&ctx, params).await;
+
+    // Whatever the outcome, this server has done its single job.
+    if let Some(tx) = ctx.shutdown.lock().ok().and_then(|mut g| g.take()) {
+        let _ = tx.send(());
+    }
+
+    match result {
+        Ok(()) => Html(page("You're signed in", "This window can be closed.")),
+        Err(e) => Html(page("Sign-in failed", &e.message())),
+    }
+}
+
+async fn exchange(ctx: &CallbackCtx, params: CallbackParams) -> Result<(), AuthError> {
+    let code = params
+        .code
+        .ok_or_else(|| AuthError::new("OAUTH_ERROR", "Missing authorization code".to_string()))?;
+    let state = params.state.unwrap_or_default();
+    if state != ctx.expected_state {
+        return Err(AuthError::new("OAUTH_ERROR", "State mismatch".to_string()));
+    }
+
+    let client = reqwest::Client::new();
+    let resp = client
+        .post(format!("{}/auth/callback", ctx.server_url))
+        .json(&serde_json::json!({ "code": code }))
+        .send()
+        .await
+        .map_err(|e| AuthError::new("OAUTH_ERROR", format!("Code exchange failed: {}", e)))?;
+
+    if !resp.status().is_success() {
+        return Err(AuthError::new(
+            "OAUTH_ERROR",
+            format!("Code exchange rejected with status {}", resp.status()),
+        ));
+    }
+
+    let body: CallbackResponse = resp
+        .json()
+        .await
+        .map_err(|e| AuthError::new("DECODE_ERROR", format!("Invalid callback response: {}", e)))?;
+
+    let summary = body.account.clone();
+    auth::store_tokens(
+        &summary.account_id,
+        &body.access_token,
+        body.refresh_token.as_deref(),
+        body.expires_at,
+    )?;
+    auth::register_account(summary.clone())?;
+    auth::set_active_account(summary.account_id.clone())?;
+
+    use tauri::Emitter;
+    let _ = ctx.app.emit("login-complete", summary);
+    Ok(())
+}
+
+// Minimal landing page shown in the user's browser after the redirect.
+fn page(title: &str, message: &str) -> String {
+    format!(
+        "<!doctype html><html><head><meta charset=\"utf-8\"><title>{title}</title></head>\
+         <body style=\"font-family: sans-serif; text-align: center; padding: 3rem;\">\
+         <h1>{title}</h1><p>{message}</p></body></html>"
+    )
+}
@@ -0,0 +1,152 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Deserialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::auth;
+
+// How long before expiry we proactively refresh, and the floor/ceiling on how
+// long the scheduler sleeps between checks.
+const REFRESH_LEAD: i64 = 60;
+const MIN_SLEEP: u64 = 5;
+const MAX_SLEEP: u64 = 15 * 60;
+// After a failed refresh we back off exponentially from this floor up to
+// `MAX_SLEEP`, so a server outage can't make the scheduler hammer
+// `/auth/refresh` (and spam `auth-expired`) every `MIN_SLEEP` seconds.
+const BACKOFF_START: u64 = 30;
+// Upper bound on the blocking refresh round-trip, so an unresponsive server
+// can't stall the caller (including the `get_auth_token` command thread) for an
+// unbounded time.
+const REFRESH_TIMEOUT: Duration = Duration::from_secs(15);
+
+// Shape of `{server_url}/auth/refresh`'s response body.
+#[derive(Debug, Deserialize)]
+struct RefreshResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_at: Option<i64>,
+}
+
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+// Exchange the stored refresh token for a fresh access token and persist it.
+// Runs synchronously (blocking HTTP) so it can be used both from the scheduler
+// and from `get_auth_token`'s inline-refresh path.
+pub(crate) fn refresh_now(account_id: &str) -> Result<(), auth::AuthError> {
+    let refresh_token = auth::get_refresh_token(account_id)?.ok_or_else(|| {
+        auth::AuthError::new("NO_REFRESH_TOKEN", "No refresh token stored".to_string())
+    })?;
+    let server_url = auth::account_server_url(account_id)?;
+
+    let client = reqwest::blocking::Client::builder()
+        .timeout(REFRESH_TIMEOUT)
+        .build()
+        .map_err(|e| auth::AuthError::new("REFRESH_ERROR", format!("Failed to build client: {}", e)))?;
+    let resp = client
+        .post(format!("{}/auth/refresh", server_url))
+        .json(&serde_json::json!({ "refresh_token": refresh_token }))
+        .send()
+        .map_err(|e| auth::AuthError::new("REFRESH_ERROR", format!("Refresh request failed: {}", e)))?;
+
+    if !resp.status().is_success() {
+        return Err(auth::AuthError::new(
+            "REFRESH_ERROR",
+            format!("Refresh rejected with status {}", resp.status()),
+        ));
+    }
+
+    let body: RefreshResponse = resp
+        .json()
+        .map_err(|e| auth::AuthError::new("DECODE_ERROR", format!("Invalid refresh response: {}", e)))?;
+
+    auth::store_tokens(
+        account_id,
+        &body.access_token,
+        body.refresh_token.as_deref(),
+        body.expires_at,
+    )
+}
+
+// Spawn the background scheduler that keeps the active account's access token
+// alive, emitting `tokens-refreshed` / `auth-expired` events to the frontend.
+pub(crate) fn spawn_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // Seconds to wait before the next attempt after a failure; `None` while
+        // the last refresh succeeded (or none was due yet).
+        let mut backoff: Option<u64> = None;
+        loop {
+            let sleep_secs = match backoff {
+                Some(secs) => secs,
+                None => next_sleep_secs().unwrap_or(MAX_SLEEP),
+            };
+            tokio::time::sleep(Duration::from_secs(sleep_secs)).await;
+
+            let account_id = match auth::get_active_account() {
+                Ok(Some(id)) => id,
+                _ => {
+                    backoff = None;
+                    continue;
+                }
+            };
+
+            // Only act once we're inside the refresh lead window.
+            match auth::read_expiry(&account_id) {
+                Ok(Some(exp)) if now_unix() >= exp - REFRESH_LEAD => {}
+                _ => {
+                    backoff = None;
+                    continue;
+                }
+            }
+
+            let id = account_id.clone();
+            let result = tauri::async_runtime::spawn_blocking(move || refresh_now(&id)).await;
+            match result {
+                Ok(Ok(())) => {
+                    backoff = None;
+                    let summary = auth::find_account(&account_id).ok().flatten();
+                    let _ = app.emit("tokens-refreshed", summary);
+                }
+                Ok(Err(e)) => {
+                    // Back off exponentially so a persistent failure doesn't
+                    // re-POST and re-emit every few seconds while still inside
+                    // the lead window.
+                    backoff = Some(next_backoff(backoff));
+                    let _ = app.emit("auth-expired", e);
+                }
+                Err(_) => {
+                    // The blocking task panicked; surface it as an expiry too.
+                    backoff = Some(next_backoff(backoff));
+                    let _ = app.emit(
+                        "auth-expired",
+                        auth::AuthError::new("REFRESH_ERROR", "Refresh task failed".to_string()),
+                    );
+                }
+            }
+        }
+    });
+}
+
+// Seconds to sleep until we should next wake for the active account, clamped so
+// a far-off (or missing) expiry doesn't pin the scheduler asleep forever.
+fn next_sleep_secs() -> Option<u64> {
+    let account_id = auth::get_active_account().ok().flatten()?;
+    let exp = auth::read_expiry(&account_id).ok().flatten()?;
+    let delta = exp - REFRESH_LEAD - now_unix();
+    Some((delta.max(MIN_SLEEP as i64) as u64).min(MAX_SLEEP))
+}
+
+// Double the previous backoff (starting at `BACKOFF_START`), capped at
+// `MAX_SLEEP`.
+fn next_backoff(prev: Option<u64>) -> u64 {
+    match prev {
+        Some(secs) => secs.saturating_mul(2).min(MAX_SLEEP),
+        None => BACKOFF_START,
+    }
+}
@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+
+use crate::error::AppError;
+use crate::ServerInfo;
+
+const SETTINGS_FILE: &str = "settings.json";
+// Key whose value, when set, overrides the build-time server url at runtime.
+const SERVER_URL_KEY: &str = "server_url";
+
+// A saved recurring auction filter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub county: Option<String>,
+    #[serde(default)]
+    pub min_price: Option<f64>,
+    #[serde(default)]
+    pub max_price: Option<f64>,
+    #[serde(default)]
+    pub sale_type: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SettingsData {
+    #[serde(default)]
+    values: HashMap<String, Value>,
+    #[serde(default)]
+    saved_searches: Vec<SavedSearch>,
+}
+
+// Durable, non-secret preferences. Reads are served from memory; writes mark
+// the store dirty and are flushed by a debounced background task.
+pub struct SettingsStore {
+    data: Mutex<SettingsData>,
+    dirty: AtomicBool,
+}
+
+impl SettingsStore {
+    pub fn new() -> Self {
+        SettingsStore {
+            data: Mutex::new(SettingsData::default()),
+            dirty: AtomicBool::new(false),
+        }
+    }
+}
+
+impl Default for SettingsStore {
+    fn default() -> Self {
+        SettingsStore::new()
+    }
+}
+
+fn state_error(e: impl std::fmt::Display) -> AppError {
+    AppError::new("STATE_ERROR", format!("Settings state poisoned: {}", e))
+}
+
+fn settings_path(app: &AppHandle) -> Result<PathBuf, AppError> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::new("PATH_ERROR", format!("No app data dir: {}", e)))?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| AppError::new("IO_ERROR", format!("Failed to create data dir: {}", e)))?;
+    Ok(dir.join(SETTINGS_FILE))
+}
+
+fn new_id() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    format!("{:x}", nanos)
+}
+
+// Load persisted settings into memory. Called from `setup`; a missing file is
+// treated as empty rather than an error.
+pub(crate) fn load(app: &AppHandle, state: &SettingsStore) -> Result<(), AppError> {
+    let path = settings_path(app)?;
+    let data = match std::fs::read(&path) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+            AppError::new("DECODE_ERROR", format!("Invalid settings file: {}", e))
+        })?,
+        Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => SettingsData::default(),
+        Err(e) => {
+            return Err(AppError::new(
+                "IO_ERROR",
+                format!("Failed to read settings: {}", e),
+            ))
+        }
+    };
+    *state.data.lock().map_err(state_error)? = data;
+    Ok(())
+}
+
+// Spawn the debounced auto-save task: flush to disk at most once per second,
+// and only when something changed since the last flush.
+pub(crate) fn spawn_autosave(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            let state = match app.try_state::<SettingsStore>() {
+                Some(s) => s,
+                None => continue,
+            };
+            if state.dirty.swap(false, Ordering::SeqCst) {
+                if let Err(e) = flush(&app, &state) {
+                    log::warn!("Failed to persist settings: {}", e.message());
+                    // Keep the change pending so the next tick retries.
+                    state.dirty.store(true, Ordering::SeqCst);
+                }
+            }
+        }
+    });
+}
+
+fn flush(app: &AppHandle, state: &SettingsStore) -> Result<(), AppError> {
+    let bytes = {
+        let data = state.data.lock().map_err(state_error)?;
+        serde_json::to_vec_pretty(&*data).map_err(|e| {
+            AppError::new("ENCODE_ERROR", format!("Failed to serialize settings: {}", e))
+        })?
+    };
+    std::fs::write(settings_path(app)?, bytes)
+        .map_err(|e| AppError::new("IO_ERROR", format!("Failed to write settings: {}", e)))
+}
+
+fn mark_dirty(state: &SettingsStore) {
+    state.dirty.store(true, Ordering::SeqCst);
+}
+
+// Read a single setting.
+#[tauri::command]
+pub fn settings_get(state: State<SettingsStore>, key: String) -> Result<Option<Value>, AppError> {
+    Ok(state.data.lock().map_err(state_error)?.values.get(&key).cloned())
+}
+
+// Write a single setting.
+#[tauri::command]
+pub fn settings_set(
+    state: State<SettingsStore>,
+    key: String,
+    value: Value,
+) -> Result<(), AppError> {
+    state.data.lock().map_err(state_error)?.values.insert(key, value);
+    mark_dirty(&state);
+    Ok(())
+}
+
+// Delete a single setting.
+#[tauri::command]
+pub fn settings_delete(state: State<SettingsStore>, key: String) -> Result<(), AppError> {
+    state.data.lock().map_err(state_error)?.values.remove(&key);
+    mark_dirty(&state);
+    Ok(())
+}
+
+// List every saved search.
+#[tauri::command]
+pub fn saved_searches_list(state: State<SettingsStore>) -> Result<Vec<SavedSearch>, AppError> {
+    Ok(state.data.lock().map_err(state_error)?.saved_searches.clone())
+}
+
+// Add a saved search, assigning it an id when the given id is empty, and return
+// the stored record.
+#[tauri::command]
+pub fn saved_searches_add(
+    state: State<SettingsStore>,
+    mut search: SavedSearch,
+) -> Result<SavedSearch, AppError> {
+    if search.id.is_empty() {
+        search.id = new_id();
+    }
+    state
+        .data
+        .lock()
+        .map_err(state_error)?
+        .saved_searches
+        .push(search.clone());
+    mark_dirty(&state);
+    Ok(search)
+}
+
+// Remove a saved search by id.
+#[tauri::command]
+pub fn saved_searches_remove(state: State<SettingsStore>, id: String) -> Result<(), AppError> {
+    state
+        .data
+        .lock()
+        .map_err(state_error)?
+        .saved_searches
+        .retain(|s| s.id != id);
+    mark_dirty(&state);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_to_empty_store() {
+        let data = SettingsData::default();
+        assert!(data.values.is_empty());
+        assert!(data.saved_searches.is_empty());
+    }
+
+    #[test]
+    fn roundtrips_through_json() {
+        let mut data = SettingsData::default();
+        data.values
+            .insert("server_url".to_string(), Value::String("https://x".to_string()));
+        data.saved_searches.push(SavedSearch {
+            id: "s1".to_string(),
+            name: "Baldwin tax sales".to_string(),
+            county: Some("Baldwin".to_string()),
+            min_price: Some(1000.0),
+            max_price: None,
+            sale_type: Some("tax".to_string()),
+        });
+
+        let json = serde_json::to_string(&data).unwrap();
+        let back: SettingsData = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(back.values.get("server_url"), data.values.get("server_url"));
+        assert_eq!(back.saved_searches.len(), 1);
+        assert_eq!(back.saved_searches[0].id, "s1");
+        assert_eq!(back.saved_searches[0].county.as_deref(), Some("Baldwin"));
+        assert_eq!(back.saved_searches[0].max_price, None);
+    }
+
+    #[test]
+    fn omitted_optional_search_fields_default_to_none() {
+        let back: SettingsData =
+            serde_json::from_str(r#"{"saved_searches":[{"id":"s2","name":"All"}]}"#).unwrap();
+        let search = &back.saved_searches[0];
+        assert_eq!(search.county, None);
+        assert_eq!(search.min_price, None);
+        assert_eq!(search.sale_type, None);
+    }
+}
+
+// Get server configuration info, letting a user-set `server_url` in the
+// settings store override the build-time default at runtime.
+#[tauri::command]
+pub fn get_server_info(state: State<SettingsStore>) -> Result<ServerInfo, AppError> {
+    let server_url = state
+        .data
+        .lock()
+        .map_err(state_error)?
+        .values
+        .get(SERVER_URL_KEY)
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .unwrap_or_else(|| crate::default_server_url().to_string());
+
+    Ok(ServerInfo {
+        server_url,
+        is_development: cfg!(debug_assertions),
+        tauri_version: env!("CARGO_PKG_VERSION").to_string(),
+    })
+}
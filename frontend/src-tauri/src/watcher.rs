@@ -0,0 +1,258 @@
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::auth;
+use crate::error::AppError;
+
+// Tray id so the polling loop can fetch the icon back and refresh its menu.
+const TRAY_ID: &str = "main";
+
+// A single watched auction and when it closes, as returned by
+// `{server_url}/watchlist/deadlines`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuctionDeadline {
+    pub id: String,
+    pub title: String,
+    pub closes_at: i64,
+}
+
+// Mutable watcher configuration, held in Tauri state.
+pub struct WatchConfig {
+    interval_secs: u64,
+    // Notification lead times in minutes before close (e.g. 15, 5, 1).
+    leads: Vec<u32>,
+    // Suppress notifications until this unix time, set by the tray "Snooze".
+    snooze_until: i64,
+    // (auction id, lead) pairs already notified, so we fire each lead once.
+    fired: HashSet<(String, u32)>,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        WatchConfig {
+            interval_secs: 60,
+            leads: vec![15, 5, 1],
+            snooze_until: 0,
+            fired: HashSet::new(),
+        }
+    }
+}
+
+pub struct WatchState(pub Mutex<WatchConfig>);
+
+impl WatchState {
+    pub fn new() -> Self {
+        WatchState(Mutex::new(WatchConfig::default()))
+    }
+}
+
+impl Default for WatchState {
+    fn default() -> Self {
+        WatchState::new()
+    }
+}
+
+fn lock_error(e: impl std::fmt::Display) -> AppError {
+    AppError::new("STATE_ERROR", format!("Watch state poisoned: {}", e))
+}
+
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+// Change how often the watcher polls for deadlines.
+#[tauri::command]
+pub fn set_watch_interval(state: State<WatchState>, secs: u64) -> Result<(), AppError> {
+    let mut cfg = state.0.lock().map_err(lock_error)?;
+    cfg.interval_secs = secs.max(5);
+    Ok(())
+}
+
+// Change the lead times (in minutes) at which closing auctions notify.
+#[tauri::command]
+pub fn set_notification_leads(state: State<WatchState>, leads: Vec<u32>) -> Result<(), AppError> {
+    let mut cfg = state.0.lock().map_err(lock_error)?;
+    cfg.leads = leads;
+    cfg.fired.clear();
+    Ok(())
+}
+
+// Build the system tray and its initial menu.
+pub(crate) fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
+    let menu = build_menu(app, &[])?;
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .menu(&menu)
+        .on_menu_event(|app, event| handle_menu_event(app, event.id().as_ref()));
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+    Ok(())
+}
+
+// Assemble a tray menu listing the next few expiring auctions above the
+// standard Open/Snooze actions.
+fn build_menu(app: &AppHandle, deadlines: &[AuctionDeadline]) -> tauri::Result<Menu<tauri::Wry>> {
+    let menu = Menu::new(app)?;
+    let mut upcoming: Vec<&AuctionDeadline> = deadlines.iter().collect();
+    upcoming.sort_by_key(|d| d.closes_at);
+    for d in upcoming.into_iter().take(5) {
+        let mins = ((d.closes_at - now_unix()) / 60).max(0);
+        let item = MenuItem::with_id(
+            app,
+            format!("auction:{}", d.id),
+            format!("{} — {}m", d.title, mins),
+            true,
+            None::<&str>,
+        )?;
+        menu.append(&item)?;
+    }
+    if !deadlines.is_empty() {
+        menu.append(&PredefinedMenuItem::separator(app)?)?;
+    }
+    menu.append(&MenuItem::with_id(app, "open", "Open", true, None::<&str>)?)?;
+    menu.append(&MenuItem::with_id(app, "snooze", "Snooze", true, None::<&str>)?)?;
+    Ok(menu)
+}
+
+fn handle_menu_event(app: &AppHandle, id: &str) {
+    match id {
+        "open" => show_main_window(app),
+        "snooze" => {
+            if let Some(state) = app.try_state::<WatchState>() {
+                if let Ok(mut cfg) = state.0.lock() {
+                    // Quiet notifications for one full poll interval.
+                    cfg.snooze_until = now_unix() + cfg.interval_secs as i64;
+                }
+            }
+        }
+        auction if auction.starts_with("auction:") => show_main_window(app),
+        _ => {}
+    }
+}
+
+// Toggle the main window's visibility, used by the global hotkey.
+pub(crate) fn toggle_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        if window.is_visible().unwrap_or(false) {
+            let _ = window.hide();
+        } else {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+    }
+}
+
+fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+// Spawn the deadline-polling loop that drives notifications and the tray menu.
+pub(crate) fn spawn_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let client = reqwest::Client::new();
+        loop {
+            let interval = app
+                .try_state::<WatchState>()
+                .and_then(|s| s.0.lock().ok().map(|c| c.interval_secs))
+                .unwrap_or(60);
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+
+            if let Ok(deadlines) = poll_deadlines(&app, &client).await {
+                if let Ok(menu) = build_menu(&app, &deadlines) {
+                    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+                        let _ = tray.set_menu(Some(menu));
+                    }
+                }
+                notify_closing(&app, &deadlines);
+            }
+        }
+    });
+}
+
+async fn poll_deadlines(
+    app: &AppHandle,
+    client: &reqwest::Client,
+) -> Result<Vec<AuctionDeadline>, AppError> {
+    let account_id = auth::get_active_account()?;
+    // `get_auth_token` may trigger a synchronous (blocking HTTP) refresh for an
+    // expired token, so run it on the blocking pool rather than stalling this
+    // async-runtime worker.
+    let lookup = account_id.clone();
+    let token = tauri::async_runtime::spawn_blocking(move || auth::get_auth_token(lookup))
+        .await
+        .map_err(|e| AppError::new("WATCH_ERROR", format!("Token lookup failed: {}", e)))??
+        .ok_or_else(|| AppError::new("NO_TOKEN", "No stored token".to_string()))?;
+    let server_url = match account_id {
+        Some(id) => auth::account_server_url(&id)?,
+        None => crate::default_server_url().to_string(),
+    };
+
+    let resp = client
+        .get(format!("{}/watchlist/deadlines", server_url))
+        .bearer_auth(token)
+        .send()
+        .await
+        .map_err(|e| AppError::new("WATCH_ERROR", format!("Deadline poll failed: {}", e)))?;
+
+    resp.json::<Vec<AuctionDeadline>>()
+        .await
+        .map_err(|e| AppError::new("DECODE_ERROR", format!("Invalid deadline response: {}", e)))
+}
+
+// Fire a native notification (and frontend event) the first time an auction
+// enters each configured lead window, unless snoozed.
+fn notify_closing(app: &AppHandle, deadlines: &[AuctionDeadline]) {
+    let state = match app.try_state::<WatchState>() {
+        Some(s) => s,
+        None => return,
+    };
+    let mut cfg = match state.0.lock() {
+        Ok(c) => c,
+        Err(_) => return,
+    };
+    // Drop fired markers for auctions that have dropped out of the watchlist
+    // (closed or unwatched) so the set can't grow without bound over a long
+    // session.
+    let current: HashSet<&str> = deadlines.iter().map(|d| d.id.as_str()).collect();
+    cfg.fired.retain(|(id, _)| current.contains(id.as_str()));
+
+    if now_unix() < cfg.snooze_until {
+        return;
+    }
+
+    let leads = cfg.leads.clone();
+    for d in deadlines {
+        let minutes_left = (d.closes_at - now_unix()) / 60;
+        if minutes_left < 0 {
+            continue;
+        }
+        for lead in &leads {
+            let key = (d.id.clone(), *lead);
+            if minutes_left <= *lead as i64 && !cfg.fired.contains(&key) {
+                cfg.fired.insert(key);
+                let _ = app
+                    .notification()
+                    .builder()
+                    .title("Auction closing soon")
+                    .body(format!("{} closes in ~{} min", d.title, minutes_left))
+                    .show();
+                let _ = app.emit("auction-closing-soon", d.clone());
+                break;
+            }
+        }
+    }
+}